@@ -0,0 +1,122 @@
+//! Generates the opcode table, conversions, mnemonics, and arity slices from
+//! `instructions.in` so the decoder, encoder, and disassembler all share one
+//! source of truth. The parsed spec is emitted to `$OUT_DIR/instrs.rs`, which
+//! `components::instructions` pulls in with `include!`.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+struct Op {
+    code: u16,
+    variant: String,
+    mnemonic: String,
+    form: String,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=instructions.in");
+
+    let spec = fs::read_to_string("instructions.in").expect("failed to read instructions.in");
+    let ops = parse(&spec);
+
+    let mut out = String::new();
+    emit_enum(&mut out, &ops);
+    emit_try_from(&mut out, &ops);
+    emit_to_u16(&mut out, &ops);
+    emit_mnemonic(&mut out, &ops);
+    emit_slices(&mut out, &ops);
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("instrs.rs");
+    fs::write(&dest, out).expect("failed to write instrs.rs");
+}
+
+fn parse(spec: &str) -> Vec<Op> {
+    spec.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .enumerate()
+        .map(|(index, line)| {
+            let mut fields = line.split_whitespace();
+            let code: u16 = fields.next().unwrap().parse().unwrap();
+            let variant = fields.next().unwrap().to_string();
+            let mnemonic = fields.next().unwrap().to_string();
+            let form = fields.next().unwrap().to_string();
+            assert_eq!(
+                code as usize, index,
+                "opcode {variant} has code {code} but must be contiguous from 0"
+            );
+            Op {
+                code,
+                variant,
+                mnemonic,
+                form,
+            }
+        })
+        .collect()
+}
+
+fn emit_enum(out: &mut String, ops: &[Op]) {
+    out.push_str("#[derive(Debug, PartialEq, Eq, Clone, Copy)]\npub enum Opcode {\n");
+    for op in ops {
+        writeln!(out, "    {},", op.variant).unwrap();
+    }
+    out.push_str("}\n\n");
+}
+
+fn emit_try_from(out: &mut String, ops: &[Op]) {
+    out.push_str("impl TryFrom<u16> for Opcode {\n");
+    out.push_str("    type Error = DecodeError;\n\n");
+    out.push_str("    fn try_from(value: u16) -> Result<Self, Self::Error> {\n");
+    out.push_str("        Ok(match value {\n");
+    for op in ops {
+        writeln!(out, "            {} => Self::{},", op.code, op.variant).unwrap();
+    }
+    out.push_str("            _ => return Err(DecodeError::UnknownOpcode(value)),\n");
+    out.push_str("        })\n    }\n}\n\n");
+}
+
+fn emit_to_u16(out: &mut String, ops: &[Op]) {
+    out.push_str("impl From<Opcode> for u16 {\n");
+    out.push_str("    fn from(value: Opcode) -> Self {\n");
+    out.push_str("        match value {\n");
+    for op in ops {
+        writeln!(out, "            Opcode::{} => {},", op.variant, op.code).unwrap();
+    }
+    out.push_str("        }\n    }\n}\n\n");
+}
+
+fn emit_mnemonic(out: &mut String, ops: &[Op]) {
+    out.push_str("impl Opcode {\n");
+    out.push_str("    /// The canonical Aleo mnemonic for this opcode, as it appears in assembly.\n");
+    out.push_str("    #[allow(dead_code)]\n");
+    out.push_str("    pub fn mnemonic(&self) -> &'static str {\n");
+    out.push_str("        match self {\n");
+    for op in ops {
+        writeln!(
+            out,
+            "            Self::{} => {:?},",
+            op.variant, op.mnemonic
+        )
+        .unwrap();
+    }
+    out.push_str("        }\n    }\n}\n\n");
+}
+
+fn emit_slices(out: &mut String, ops: &[Op]) {
+    let slice = |out: &mut String, name: &str, forms: &[&str]| {
+        writeln!(out, "#[allow(dead_code)]").unwrap();
+        writeln!(out, "const {name}: &[Opcode] = &[").unwrap();
+        for op in ops.iter().filter(|op| forms.contains(&op.form.as_str())) {
+            writeln!(out, "    Opcode::{},", op.variant).unwrap();
+        }
+        out.push_str("];\n\n");
+    };
+
+    slice(out, "UNARY", &["unary"]);
+    // Equality checks share the binary decode path.
+    slice(out, "BINARY", &["binary", "is_check"]);
+    slice(out, "ASSERT", &["assert"]);
+    slice(out, "IS_CHECK", &["is_check"]);
+}
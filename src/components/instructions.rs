@@ -3,199 +3,150 @@ use core::num;
 use crate::ByteCode;
 use crate::util;
 use super::registers::Register;
-use super::types::Literal;
-
-#[derive(Debug, PartialEq, Clone, Copy)]
-pub enum Opcode {
-    Abs,
-    AbsWrapped,
-    Add,
-    AddWrapped,
-    And,
-    AssertEq,
-    AssertNeq,
-    Call,
+use super::types::{Literal, LiteralType};
+
+// The `Opcode` enum, its `TryFrom<u16>`/`From<Opcode> for u16` conversions, the
+// `mnemonic` table, and the `UNARY`/`BINARY`/`ASSERT`/`IS_CHECK` arity slices are
+// all generated by `build.rs` from `instructions.in` so the numeric codes,
+// mnemonics, and arity classification can never drift out of lockstep.
+include!(concat!(env!("OUT_DIR"), "/instrs.rs"));
+
+/// An error produced while decoding bytecode into structured instructions.
+///
+/// Every read path returns one of these instead of panicking, so malformed or
+/// truncated on-chain bytecode can be rejected gracefully. The offending raw
+/// value is carried where available so a disassembler can still report it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DecodeError {
+    /// The opcode `u16` does not correspond to a known instruction.
+    UnknownOpcode(u16),
+    /// The opcode is known but its instruction form has no reader yet (e.g. `cast`).
+    UnsupportedOpcode(Opcode),
+    /// The operand tag byte is not one of `0..=3`.
+    UnknownOperandTag(u8),
+    /// The input ended before a value could be fully read.
+    UnexpectedEof,
+    /// A program locator or identifier was not valid UTF-8 or otherwise malformed.
+    InvalidLocator,
+}
+
+/// An error produced while encoding a structured instruction back to bytecode.
+///
+/// Encoding can only fail on hand-built instructions that violate an invariant
+/// the decoder always upholds, so anything produced by [`Instruction::read`]
+/// round-trips without error.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EncodeError {
+    /// A `ProgramId` operand carried an internal locator; operands only encode external ones.
+    NonExternalProgramId,
+    /// A `Call` instruction was missing its callee locator.
+    MissingCallee,
+    /// A `Call` instruction did not carry a `Multiple` output.
+    MalformedCallOutput,
+}
+
+/// The semantic family an [`Opcode`] belongs to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OpClass {
+    Arithmetic,
+    Bitwise,
+    Comparison,
+    Hash,
+    Commit,
+    Assert,
     Cast,
-    CommitBHP256,
-    CommitBHP512,
-    CommitBHP768,
-    CommitBHP1024,
-    CommitPED64,
-    CommitPED128,
-    Div,
-    DivWrapped,
-    Double,
-    GreaterThan,
-    GreaterThanOrEqual,
-    HashBHP256,
-    HashBHP512,
-    HashBHP768,
-    HashBHP1024,
-    HashPED64,
-    HashPED128,
-    HashPSD2,
-    HashPSD4,
-    HashPSD8,
-    Inv,
-    IsEq,
-    IsNeq,
-    LessThan,
-    LessThanOrEqual,
-    Mod,
-    Mul,
-    MulWrapped,
-    Nand,
-    Neg,
-    Nor,
-    Not,
-    Or,
-    Pow,
-    PowWrapped,
-    Rem,
-    RemWrapped,
-    Shl,
-    ShlWrapped,
-    Shr,
-    ShrWrapped,
-    Square,
-    SquareRoot,
-    Sub,
-    SubWrapped,
+    Call,
     Ternary,
-    Xor,
 }
 
-impl From<u16> for Opcode {
-    fn from(value: u16) -> Self {
-        match value {
-            0 => Self::Abs,
-            1 => Self::AbsWrapped,
-            2 => Self::Add,
-            3 => Self::AddWrapped,
-            4 => Self::And,
-            5 => Self::AssertEq,
-            6 => Self::AssertNeq,
-            7 => Self::Call,
-            8 => Self::Cast,
-            9 => Self::CommitBHP256,
-            10 => Self::CommitBHP512,
-            11 => Self::CommitBHP768,
-            12 => Self::CommitBHP1024,
-            13 => Self::CommitPED64,
-            14 => Self::CommitPED128,
-            15 => Self::Div,
-            16 => Self::DivWrapped,
-            17 => Self::Double,
-            18 => Self::GreaterThan,
-            19 => Self::GreaterThanOrEqual,
-            20 => Self::HashBHP256,
-            21 => Self::HashBHP512,
-            22 => Self::HashBHP768,
-            23 => Self::HashBHP1024,
-            24 => Self::HashPED64,
-            25 => Self::HashPED128,
-            26 => Self::HashPSD2,
-            27 => Self::HashPSD4,
-            28 => Self::HashPSD8,
-            29 => Self::Inv,
-            30 => Self::IsEq,
-            31 => Self::IsNeq,
-            32 => Self::LessThan,
-            33 => Self::LessThanOrEqual,
-            34 => Self::Mod,
-            35 => Self::Mul,
-            36 => Self::MulWrapped,
-            37 => Self::Nand,
-            38 => Self::Neg,
-            39 => Self::Nor,
-            40 => Self::Not,
-            41 => Self::Or,
-            42 => Self::Pow,
-            43 => Self::PowWrapped,
-            44 => Self::Rem,
-            45 => Self::RemWrapped,
-            46 => Self::Shl,
-            47 => Self::ShlWrapped,
-            48 => Self::Shr,
-            49 => Self::ShrWrapped,
-            50 => Self::Square,
-            51 => Self::SquareRoot,
-            52 => Self::Sub,
-            53 => Self::SubWrapped,
-            54 => Self::Ternary,
-            55 => Self::Xor,
-            _ => unreachable!(),
+/// The shape of the destination an instruction writes to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum OutputKind {
+    /// Exactly one destination register (unary, binary, ternary, hash, …).
+    Single,
+    /// A dynamic number of destination registers (`call`).
+    Multiple,
+    /// No destination at all (`assert.eq`, `assert.neq`).
+    None,
+}
+
+impl Opcode {
+    /// The fixed number of input operands this opcode takes, or `None` when it
+    /// has no fixed count (`call`, whose count is read at decode time, and `cast`,
+    /// which the reader rejects as unsupported).
+    pub fn arity(&self) -> Option<u8> {
+        match self {
+            Opcode::Call | Opcode::Cast => None,
+            Opcode::Ternary => Some(3),
+            o if ASSERT.contains(o) => Some(2),
+            o if UNARY.contains(o) => Some(1),
+            o if BINARY.contains(o) => Some(2),
+            _ => None,
+        }
+    }
+
+    /// The semantic family this opcode belongs to.
+    pub fn category(&self) -> OpClass {
+        match self {
+            Opcode::Call => OpClass::Call,
+            Opcode::Cast => OpClass::Cast,
+            Opcode::Ternary => OpClass::Ternary,
+            Opcode::AssertEq | Opcode::AssertNeq => OpClass::Assert,
+            Opcode::HashBHP256
+            | Opcode::HashBHP512
+            | Opcode::HashBHP768
+            | Opcode::HashBHP1024
+            | Opcode::HashPED64
+            | Opcode::HashPED128
+            | Opcode::HashPSD2
+            | Opcode::HashPSD4
+            | Opcode::HashPSD8 => OpClass::Hash,
+            Opcode::CommitBHP256
+            | Opcode::CommitBHP512
+            | Opcode::CommitBHP768
+            | Opcode::CommitBHP1024
+            | Opcode::CommitPED64
+            | Opcode::CommitPED128 => OpClass::Commit,
+            Opcode::GreaterThan
+            | Opcode::GreaterThanOrEqual
+            | Opcode::LessThan
+            | Opcode::LessThanOrEqual
+            | Opcode::IsEq
+            | Opcode::IsNeq => OpClass::Comparison,
+            Opcode::And
+            | Opcode::Or
+            | Opcode::Xor
+            | Opcode::Nand
+            | Opcode::Nor
+            | Opcode::Not
+            | Opcode::Shl
+            | Opcode::ShlWrapped
+            | Opcode::Shr
+            | Opcode::ShrWrapped => OpClass::Bitwise,
+            _ => OpClass::Arithmetic,
+        }
+    }
+
+    /// The destination shape this opcode produces.
+    ///
+    /// Note that hash and commit ops are unary/binary with a single typed
+    /// output, whereas `call` emits a dynamic number of destinations.
+    pub fn output_shape(&self) -> OutputKind {
+        match self {
+            Opcode::Call => OutputKind::Multiple,
+            o if ASSERT.contains(o) => OutputKind::None,
+            _ => OutputKind::Single,
         }
     }
 }
 
-const UNARY: &[Opcode] = &[
-    Opcode::Abs,
-    Opcode::AbsWrapped,
-    Opcode::Double,
-    Opcode::Inv,
-    Opcode::Neg,
-    Opcode::Not,
-    Opcode::Square,
-    Opcode::SquareRoot,
-    Opcode::HashBHP256,
-    Opcode::HashBHP512,
-    Opcode::HashBHP768,
-    Opcode::HashBHP1024,
-    Opcode::HashPED64,
-    Opcode::HashPED128,
-    Opcode::HashPSD2,
-    Opcode::HashPSD4,
-    Opcode::HashPSD8,
-];
-
-const BINARY: &[Opcode] = &[
-    Opcode::Add,
-    Opcode::AddWrapped,
-    Opcode::Sub,
-    Opcode::SubWrapped,
-    Opcode::Mul,
-    Opcode::MulWrapped,
-    Opcode::Div,
-    Opcode::DivWrapped,
-    Opcode::Rem,
-    Opcode::RemWrapped,
-    Opcode::Pow,
-    Opcode::PowWrapped,
-    Opcode::Shl,
-    Opcode::ShlWrapped,
-    Opcode::Shr,
-    Opcode::ShrWrapped,
-    Opcode::And,
-    Opcode::Xor,
-    Opcode::Or,
-    Opcode::Nand,
-    Opcode::Nor,
-    Opcode::GreaterThan,
-    Opcode::GreaterThanOrEqual,
-    Opcode::LessThan,
-    Opcode::LessThanOrEqual,
-    Opcode::IsEq,
-    Opcode::IsNeq,
-    Opcode::CommitBHP256,
-    Opcode::CommitBHP512,
-    Opcode::CommitBHP768,
-    Opcode::CommitBHP1024,
-    Opcode::CommitPED64,
-    Opcode::CommitPED128,
-    Opcode::Mod,
-];
-
-const ASSERT: &[Opcode] = &[Opcode::AssertEq, Opcode::AssertNeq];
-const IS_CHECK: &[Opcode] = &[Opcode::IsEq, Opcode::IsNeq];
-
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Locator {
     Internal(String),
     External((String, String, String))
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Operand {
     Literal(Literal),
     Register(Register),
@@ -204,105 +155,492 @@ pub enum Operand {
 }
 
 impl Operand {
-    fn read(bytes: &mut ByteCode) -> Self {
-        match bytes.read_u8() {
-            0 => Self::Literal(Literal::read(bytes)),
-            1 => Self::Register(Register::read(bytes)),
-            2 => Self::ProgramId(Locator::External(util::read_locator(bytes))),
+    fn read(bytes: &mut ByteCode) -> Result<Self, DecodeError> {
+        Ok(match bytes.read_u8()? {
+            0 => Self::Literal(Literal::read(bytes)?),
+            1 => Self::Register(Register::read(bytes)?),
+            2 => Self::ProgramId(Locator::External(util::read_locator(bytes)?)),
             3 => Self::Caller,
-            _ => unreachable!(),    
+            tag => return Err(DecodeError::UnknownOperandTag(tag)),
+        })
+    }
+
+    fn write(&self, bytes: &mut ByteCode) -> Result<(), EncodeError> {
+        match self {
+            Self::Literal(literal) => {
+                bytes.write_u8(0);
+                literal.write(bytes);
+            }
+            Self::Register(register) => {
+                bytes.write_u8(1);
+                register.write(bytes);
+            }
+            Self::ProgramId(Locator::External(locator)) => {
+                bytes.write_u8(2);
+                util::write_locator(bytes, locator);
+            }
+            Self::ProgramId(Locator::Internal(_)) => return Err(EncodeError::NonExternalProgramId),
+            Self::Caller => bytes.write_u8(3),
         }
+        Ok(())
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum Output {
     Single(Register),
     Multiple(Vec<Register>),
     None,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Instruction {
     opcode: Opcode,
+    callee: Option<Locator>,
     operands: Option<Vec<Operand>>,
     output: Output,
+    destination_type: Option<LiteralType>,
 }
 
 impl Instruction {
-    fn read_operands(bytes: &mut ByteCode, n: u8) -> Option<Vec<Operand>> {
+    fn read_operands(bytes: &mut ByteCode, n: u8) -> Result<Option<Vec<Operand>>, DecodeError> {
         if n == 0 {
-            None
+            Ok(None)
         } else {
-            Some((0..n).map(|_| Operand::read(bytes)).collect())
-        }   
+            Ok(Some((0..n).map(|_| Operand::read(bytes)).collect::<Result<_, _>>()?))
+        }
+    }
+
+    /// Reads the destination literal type that hash and commit ops carry after
+    /// their output register (e.g. the `field` in `hash.bhp256 r0 into r1 as field`).
+    /// Every other form leaves the output untyped.
+    fn read_destination_type(
+        bytes: &mut ByteCode,
+        opcode: Opcode,
+    ) -> Result<Option<LiteralType>, DecodeError> {
+        Ok(match opcode.category() {
+            OpClass::Hash | OpClass::Commit => Some(LiteralType::read(bytes)?),
+            _ => None,
+        })
     }
 
-    fn read_call_instruction(bytes: &mut ByteCode) -> Self {
-        let callee = match bytes.read_u8() {
-            1 => Locator::Internal(util::read_identifier(bytes)),
-            _ => Locator::External(util::read_locator(bytes)),
+    fn read_call_instruction(bytes: &mut ByteCode) -> Result<Self, DecodeError> {
+        let callee = match bytes.read_u8()? {
+            1 => Locator::Internal(util::read_identifier(bytes)?),
+            _ => Locator::External(util::read_locator(bytes)?),
         };
 
-        let num_inputs = bytes.read_u8();
-        let operands = Self::read_operands(bytes, num_inputs);
-        let num_outputs = bytes.read_u8();
-        let output = Output::Multiple((0..num_outputs).map(|_| Register::read(bytes)).collect());
-        
-        Self {
+        let num_inputs = bytes.read_u8()?;
+        let operands = Self::read_operands(bytes, num_inputs)?;
+        let num_outputs = bytes.read_u8()?;
+        let output = Output::Multiple(
+            (0..num_outputs)
+                .map(|_| Register::read(bytes))
+                .collect::<Result<_, _>>()?,
+        );
+
+        Ok(Self {
             opcode: Opcode::Call,
+            callee: Some(callee),
             operands,
             output,
-        }
+            destination_type: None,
+        })
     }
 
-    fn read_assert_instruction(bytes: &mut ByteCode, opcode: Opcode) -> Self {
-        Self {
+    fn read_assert_instruction(bytes: &mut ByteCode, opcode: Opcode) -> Result<Self, DecodeError> {
+        Ok(Self {
             opcode,
-            operands: Self::read_operands(bytes, 2),
+            callee: None,
+            operands: Self::read_operands(bytes, 2)?,
             output: Output::None,
-        }
+            destination_type: None,
+        })
     }
 
-    fn read_ternary_instruction(bytes: &mut ByteCode, opcode: Opcode) -> Self {
-        Self {
+    fn read_ternary_instruction(bytes: &mut ByteCode, opcode: Opcode) -> Result<Self, DecodeError> {
+        Ok(Self {
             opcode,
-            operands: Self::read_operands(bytes, 3),
-            output: Output::Single(Register::read(bytes)),
-        }
+            callee: None,
+            operands: Self::read_operands(bytes, 3)?,
+            output: Output::Single(Register::read(bytes)?),
+            destination_type: None,
+        })
     }
 
-    fn read_unary_instruction(bytes: &mut ByteCode, opcode: Opcode) -> Self {
-        Self {
+    fn read_unary_instruction(bytes: &mut ByteCode, opcode: Opcode) -> Result<Self, DecodeError> {
+        let operands = Self::read_operands(bytes, 1)?;
+        let output = Output::Single(Register::read(bytes)?);
+        Ok(Self {
             opcode,
-            operands: Self::read_operands(bytes, 1),
-            output: Output::Single(Register::read(bytes)),
-        }
+            callee: None,
+            operands,
+            output,
+            destination_type: Self::read_destination_type(bytes, opcode)?,
+        })
     }
 
-    fn read_binary_instruction(bytes: &mut ByteCode, opcode: Opcode) -> Self {
-        Self {
+    fn read_binary_instruction(bytes: &mut ByteCode, opcode: Opcode) -> Result<Self, DecodeError> {
+        let operands = Self::read_operands(bytes, 2)?;
+        let output = Output::Single(Register::read(bytes)?);
+        Ok(Self {
             opcode,
-            operands: Self::read_operands(bytes, 2),
-            output: Output::Single(Register::read(bytes)),
+            callee: None,
+            operands,
+            output,
+            destination_type: Self::read_destination_type(bytes, opcode)?,
+        })
+    }
+
+    pub fn read_instructions(bytes: &mut ByteCode) -> Result<(u32, Vec<Self>), DecodeError> {
+        let num = bytes.read_u32()?;
+        let instructions = (0..num)
+            .map(|_| Self::read(bytes))
+            .collect::<Result<_, _>>()?;
+        Ok((num, instructions))
+    }
+
+    pub fn write_instructions(
+        bytes: &mut ByteCode,
+        instructions: &[Self],
+    ) -> Result<(), EncodeError> {
+        bytes.write_u32(instructions.len() as u32);
+        for instruction in instructions {
+            instruction.write(bytes)?;
         }
+        Ok(())
     }
 
-    pub fn read_instructions(bytes: &mut ByteCode) -> (u32, Vec<Self>) {
-        let num = bytes.read_u32();
-        let instructions = (0..num).map(|_| Self::read(bytes)).collect();
-        (num, instructions)
+    pub fn write(&self, bytes: &mut ByteCode) -> Result<(), EncodeError> {
+        bytes.write_u16(u16::from(self.opcode));
+
+        if let Opcode::Call = self.opcode {
+            match &self.callee {
+                Some(Locator::Internal(identifier)) => {
+                    bytes.write_u8(1);
+                    util::write_identifier(bytes, identifier);
+                }
+                Some(Locator::External(locator)) => {
+                    bytes.write_u8(0);
+                    util::write_locator(bytes, locator);
+                }
+                None => return Err(EncodeError::MissingCallee),
+            }
+
+            let num_inputs = self.operands.as_ref().map_or(0, Vec::len);
+            bytes.write_u8(num_inputs as u8);
+            if let Some(operands) = &self.operands {
+                for operand in operands {
+                    operand.write(bytes)?;
+                }
+            }
+
+            match &self.output {
+                Output::Multiple(registers) => {
+                    bytes.write_u8(registers.len() as u8);
+                    for register in registers {
+                        register.write(bytes);
+                    }
+                }
+                _ => return Err(EncodeError::MalformedCallOutput),
+            }
+
+            return Ok(());
+        }
+
+        if let Some(operands) = &self.operands {
+            for operand in operands {
+                operand.write(bytes)?;
+            }
+        }
+
+        if let Output::Single(register) = &self.output {
+            register.write(bytes);
+        }
+
+        if let Some(destination_type) = &self.destination_type {
+            destination_type.write(bytes);
+        }
+
+        Ok(())
     }
 
-    pub fn read(bytes: &mut ByteCode) -> Self {
-        let opcode = Opcode::from(bytes.read_u16());
+    pub fn read(bytes: &mut ByteCode) -> Result<Self, DecodeError> {
+        let opcode = Opcode::try_from(bytes.read_u16()?)?;
         match opcode {
             Opcode::Call => Self::read_call_instruction(bytes),
             Opcode::Ternary => Self::read_ternary_instruction(bytes, opcode),
             o if ASSERT.contains(&o) => Self::read_assert_instruction(bytes, opcode),
             o if UNARY.contains(&o) => Self::read_unary_instruction(bytes, opcode),
             o if BINARY.contains(&o) => Self::read_binary_instruction(bytes, opcode),
-            _ => unreachable!(),
+            _ => Err(DecodeError::UnsupportedOpcode(opcode)),
         }
     }
+}
+
+#[cfg(feature = "disasm")]
+impl core::fmt::Display for Opcode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.mnemonic())
+    }
+}
+
+#[cfg(feature = "disasm")]
+impl core::fmt::Display for Locator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Internal(name) => f.write_str(name),
+            Self::External((program, network, resource)) => {
+                write!(f, "{program}.{network}/{resource}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "disasm")]
+impl core::fmt::Display for Operand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Literal(literal) => write!(f, "{literal}"),
+            Self::Register(register) => write!(f, "{register}"),
+            Self::ProgramId(locator) => write!(f, "{locator}"),
+            Self::Caller => f.write_str("self.caller"),
+        }
+    }
+}
+
+#[cfg(feature = "disasm")]
+impl core::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.opcode)?;
+
+        if let Some(callee) = &self.callee {
+            write!(f, " {callee}")?;
+        }
+
+        if let Some(operands) = &self.operands {
+            for operand in operands {
+                write!(f, " {operand}")?;
+            }
+        }
+
+        match &self.output {
+            Output::Single(register) => write!(f, " into {register}")?,
+            Output::Multiple(registers) => {
+                f.write_str(" into")?;
+                for register in registers {
+                    write!(f, " {register}")?;
+                }
+            }
+            Output::None => {}
+        }
+
+        if let Some(destination_type) = &self.destination_type {
+            write!(f, " as {destination_type}")?;
+        }
+
+        f.write_str(";")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reg(n: u64) -> Register {
+        Register::Locator(n)
+    }
+
+    /// Encodes an instruction and decodes it straight back out of the same buffer.
+    fn round_trip(instruction: &Instruction) -> Instruction {
+        let mut bytes = ByteCode::new(Vec::new());
+        instruction.write(&mut bytes).unwrap();
+        Instruction::read(&mut bytes).unwrap()
+    }
+
+    #[test]
+    fn round_trips_binary() {
+        let instruction = Instruction {
+            opcode: Opcode::Add,
+            callee: None,
+            operands: Some(vec![Operand::Register(reg(0)), Operand::Register(reg(1))]),
+            output: Output::Single(reg(2)),
+            destination_type: None,
+        };
+        assert_eq!(round_trip(&instruction), instruction);
+    }
+
+    #[test]
+    fn round_trips_assert_with_caller() {
+        let instruction = Instruction {
+            opcode: Opcode::AssertEq,
+            callee: None,
+            operands: Some(vec![Operand::Caller, Operand::Caller]),
+            output: Output::None,
+            destination_type: None,
+        };
+        assert_eq!(round_trip(&instruction), instruction);
+    }
+
+    #[test]
+    fn round_trips_hash_with_destination_type() {
+        let instruction = Instruction {
+            opcode: Opcode::HashBHP256,
+            callee: None,
+            operands: Some(vec![Operand::Register(reg(0))]),
+            output: Output::Single(reg(1)),
+            destination_type: Some(LiteralType::Field),
+        };
+        assert_eq!(round_trip(&instruction), instruction);
+    }
+
+    #[test]
+    fn round_trips_external_call() {
+        let instruction = Instruction {
+            opcode: Opcode::Call,
+            callee: Some(Locator::External((
+                "credits".to_string(),
+                "aleo".to_string(),
+                "transfer".to_string(),
+            ))),
+            operands: Some(vec![Operand::Register(reg(0)), Operand::Register(reg(1))]),
+            output: Output::Multiple(vec![reg(2), reg(3)]),
+            destination_type: None,
+        };
+        assert_eq!(round_trip(&instruction), instruction);
+    }
+
+    #[test]
+    fn encoding_internal_program_id_operand_errors() {
+        let instruction = Instruction {
+            opcode: Opcode::AssertEq,
+            callee: None,
+            operands: Some(vec![
+                Operand::ProgramId(Locator::Internal("credits".to_string())),
+                Operand::Caller,
+            ]),
+            output: Output::None,
+            destination_type: None,
+        };
+        let mut bytes = ByteCode::new(Vec::new());
+        assert_eq!(
+            instruction.write(&mut bytes),
+            Err(EncodeError::NonExternalProgramId)
+        );
+    }
+
+    #[cfg(feature = "disasm")]
+    #[test]
+    fn renders_binary() {
+        let instruction = Instruction {
+            opcode: Opcode::Add,
+            callee: None,
+            operands: Some(vec![Operand::Register(reg(0)), Operand::Register(reg(1))]),
+            output: Output::Single(reg(2)),
+            destination_type: None,
+        };
+        assert_eq!(instruction.to_string(), "add r0 r1 into r2;");
+    }
+
+    #[cfg(feature = "disasm")]
+    #[test]
+    fn renders_external_call() {
+        let instruction = Instruction {
+            opcode: Opcode::Call,
+            callee: Some(Locator::External((
+                "credits".to_string(),
+                "aleo".to_string(),
+                "transfer".to_string(),
+            ))),
+            operands: Some(vec![Operand::Register(reg(0)), Operand::Register(reg(1))]),
+            output: Output::Multiple(vec![reg(3), reg(4)]),
+            destination_type: None,
+        };
+        assert_eq!(
+            instruction.to_string(),
+            "call credits.aleo/transfer r0 r1 into r3 r4;"
+        );
+    }
+
+    #[cfg(feature = "disasm")]
+    #[test]
+    fn renders_hash_with_destination_type() {
+        let instruction = Instruction {
+            opcode: Opcode::HashBHP256,
+            callee: None,
+            operands: Some(vec![Operand::Register(reg(0))]),
+            output: Output::Single(reg(1)),
+            destination_type: Some(LiteralType::Field),
+        };
+        assert_eq!(instruction.to_string(), "hash.bhp256 r0 into r1 as field;");
+    }
+
+    #[cfg(feature = "disasm")]
+    #[test]
+    fn renders_ternary() {
+        let instruction = Instruction {
+            opcode: Opcode::Ternary,
+            callee: None,
+            operands: Some(vec![
+                Operand::Register(reg(0)),
+                Operand::Register(reg(1)),
+                Operand::Register(reg(2)),
+            ]),
+            output: Output::Single(reg(3)),
+            destination_type: None,
+        };
+        assert_eq!(instruction.to_string(), "ternary r0 r1 r2 into r3;");
+    }
+
+    #[cfg(feature = "disasm")]
+    #[test]
+    fn renders_caller_operand() {
+        let instruction = Instruction {
+            opcode: Opcode::AssertEq,
+            callee: None,
+            operands: Some(vec![Operand::Caller, Operand::Caller]),
+            output: Output::None,
+            destination_type: None,
+        };
+        assert_eq!(instruction.to_string(), "assert.eq self.caller self.caller;");
+    }
+
+    #[test]
+    fn unknown_opcode_errors() {
+        let mut bytes = ByteCode::new(Vec::new());
+        bytes.write_u16(9999);
+        assert_eq!(
+            Instruction::read(&mut bytes),
+            Err(DecodeError::UnknownOpcode(9999))
+        );
+    }
+
+    #[test]
+    fn unknown_operand_tag_errors() {
+        let mut bytes = ByteCode::new(Vec::new());
+        bytes.write_u16(u16::from(Opcode::Add));
+        bytes.write_u8(99);
+        assert_eq!(
+            Instruction::read(&mut bytes),
+            Err(DecodeError::UnknownOperandTag(99))
+        );
+    }
+
+    #[test]
+    fn truncated_input_errors() {
+        let mut bytes = ByteCode::new(Vec::new());
+        bytes.write_u16(u16::from(Opcode::Add));
+        // The two operands never follow, so the first read runs off the end.
+        assert_eq!(Instruction::read(&mut bytes), Err(DecodeError::UnexpectedEof));
+    }
+
+    #[test]
+    fn unsupported_cast_errors() {
+        let mut bytes = ByteCode::new(Vec::new());
+        bytes.write_u16(u16::from(Opcode::Cast));
+        assert_eq!(
+            Instruction::read(&mut bytes),
+            Err(DecodeError::UnsupportedOpcode(Opcode::Cast))
+        );
+    }
 }
\ No newline at end of file